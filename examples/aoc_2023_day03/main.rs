@@ -8,7 +8,7 @@ https://adventofcode.com/2023/day/3
 Part 1: sum all numbers that are touching any symbol
 Part 2: sum multiples of numbers whenever the * symbol touches exactly two numbers
 */
-use gridthings::{Cell, Grid, GridFromString};
+use gridthings::{Cell, Grid};
 
 // Helper struct to represent contiguous Numbers in a Grid<char>
 #[derive(Debug, Clone)]
@@ -82,25 +82,14 @@ fn main() {
 .664.598..
 "#;
     println!("Input:\n{}", text);
-    let grid: Grid<char> = Grid::from_string(text);
+    let grid: Grid<char> = Grid::from_chars(text);
 
-    // Extract all Numbers
-    let mut numbers: Vec<Number> = Vec::new();
-    let mut current_collection = Vec::new(); // collect numeric characters to make a Number
-    for row in grid.rows() {
-        for cell in row {
-            if cell.value.is_digit(10) {
-                current_collection.push(cell.clone());
-            } else if !current_collection.is_empty() {
-                numbers.push(Number::new(current_collection.clone()));
-                current_collection.clear();
-            }
-        }
-        if !current_collection.is_empty() {
-            numbers.push(Number::new(current_collection.clone()));
-            current_collection.clear();
-        }
-    }
+    // Extract all Numbers: each maximal horizontal run of digit cells is one Number
+    let numbers: Vec<Number> = grid
+        .runs_by_row(|cell| cell.value.is_digit(10))
+        .into_iter()
+        .map(Number::new)
+        .collect();
 
     println!("Gathered {} numbers\n", numbers.len());
     println!("Part 1: Identify numbers touching a symbol, and sum their value");
@@ -1,5 +1,47 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
 use std::hash::Hash;
 
+mod pathfinding;
+mod sparse;
+
+pub use sparse::SparseGrid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    InvalidChar {
+        y: usize,
+        x: usize,
+        c: char,
+    },
+    RaggedRow {
+        y: usize,
+        expected_width: usize,
+        actual_width: usize,
+    },
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridParseError::InvalidChar { y, x, c } => {
+                write!(f, "could not parse character '{}' at ({}, {})", c, y, x)
+            }
+            GridParseError::RaggedRow {
+                y,
+                expected_width,
+                actual_width,
+            } => write!(
+                f,
+                "row {} has width {}, expected {} (rows must be the same length)",
+                y, actual_width, expected_width
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Cell<T>
 where
@@ -15,56 +57,147 @@ pub struct Grid<T>
 where
     T: Clone + PartialEq + Eq + Hash,
 {
-    data: Vec<Vec<Cell<T>>>,
+    width: usize,
+    height: usize,
+    // flat, row-major buffer; cell (y, x) lives at index y * width + x
+    data: Vec<Cell<T>>,
 }
 
-pub trait GridFromString<T> {
-    fn from_string(text: &str) -> Self;
-}
+impl<T> Grid<T>
+where
+    T: Clone + PartialEq + Eq + Hash,
+{
+    // Build a Grid from already-parsed rows, flattening them into the backing buffer. Trims
+    // only *contiguous* leading/trailing blank rows (e.g. a leading/trailing newline in a raw
+    // string literal) and renumbers `y` to match each row's position in the buffer, so flat
+    // y * width + x addressing stays contiguous. A blank or differently-sized row anywhere else
+    // is a ragged grid, which flat addressing can't represent, so that's rejected with an error
+    // rather than silently dropped or indexed out of bounds.
+    fn from_rows(rows: Vec<Vec<Cell<T>>>) -> Result<Self, GridParseError> {
+        let leading_blank = rows.iter().take_while(|row| row.is_empty()).count();
+        let trailing_blank = rows[leading_blank..]
+            .iter()
+            .rev()
+            .take_while(|row| row.is_empty())
+            .count();
+        let kept = rows.len() - leading_blank - trailing_blank;
+        let rows: Vec<Vec<Cell<T>>> = rows
+            .into_iter()
+            .skip(leading_blank)
+            .take(kept)
+            .enumerate()
+            .map(|(y, row)| {
+                row.into_iter()
+                    .map(|cell| Cell {
+                        y,
+                        x: cell.x,
+                        value: cell.value,
+                    })
+                    .collect()
+            })
+            .collect();
 
-impl GridFromString<char> for Grid<char> {
-    fn from_string(text: &str) -> Self {
-        let mut data = Vec::new();
-        for (y, line) in text.lines().enumerate() {
-            let mut row = Vec::new();
-            for (x, c) in line.chars().enumerate() {
-                row.push(Cell { y, x, value: c });
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        for (y, row) in rows.iter().enumerate() {
+            if row.len() != width {
+                return Err(GridParseError::RaggedRow {
+                    y,
+                    expected_width: width,
+                    actual_width: row.len(),
+                });
             }
-            data.push(row);
         }
-        Grid { data }
+
+        let data = rows.into_iter().flatten().collect();
+        Ok(Grid {
+            width,
+            height,
+            data,
+        })
     }
 }
 
-impl GridFromString<i32> for Grid<i32> {
-    fn from_string(text: &str) -> Self {
-        let mut data = Vec::new();
+impl<T> Grid<T>
+where
+    T: Clone + PartialEq + Eq + Hash,
+{
+    // Parse text into a Grid<T>, calling `f` with each character's coordinates and the
+    // character itself so callers can build any cell value without a dedicated
+    // GridFromString impl. Returns an error instead of panicking when `f` rejects a character.
+    pub fn from_string_with<F>(text: &str, mut f: F) -> Result<Self, GridParseError>
+    where
+        F: FnMut(usize, usize, char) -> Option<T>,
+    {
+        let mut rows = Vec::new();
         for (y, line) in text.lines().enumerate() {
             let mut row = Vec::new();
             for (x, c) in line.chars().enumerate() {
-                // Give error message including character if we can't coerce to i32
-                let value = c
-                    .to_digit(10)
-                    .expect(&format!("Failed to cast to i32: {}", c))
-                    as i32;
-                row.push(Cell { y, x, value });
+                match f(y, x, c) {
+                    Some(value) => row.push(Cell { y, x, value }),
+                    None => return Err(GridParseError::InvalidChar { y, x, c }),
+                }
             }
-            data.push(row);
+            rows.push(row);
         }
-        Grid { data }
+        Grid::from_rows(rows)
     }
-}
 
-impl<T> Grid<T>
-where
-    T: Clone + PartialEq + Eq + Hash,
-{
-    pub fn rows(&self) -> impl Iterator<Item = &Vec<Cell<T>>> {
-        self.data.iter()
+    // (height, width) of the grid
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    fn index(&self, y: usize, x: usize) -> Option<usize> {
+        if y < self.height && x < self.width {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell<T>]> {
+        // chunks() panics on a zero chunk size, which an empty or all-blank-lines grid produces
+        self.data.chunks(self.width.max(1))
     }
 
     pub fn get(&self, y: usize, x: usize) -> Option<&Cell<T>> {
-        self.data.get(y).and_then(|row| row.get(x))
+        self.index(y, x).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, y: usize, x: usize) -> Option<&mut Cell<T>> {
+        let i = self.index(y, x)?;
+        Some(&mut self.data[i])
+    }
+
+    // Overwrite the value at (y, x) in place, leaving its coordinates untouched. No-op if
+    // (y, x) is out of bounds.
+    pub fn set(&mut self, y: usize, x: usize, value: T) {
+        if let Some(cell) = self.get_mut(y, x) {
+            cell.value = value;
+        }
+    }
+
+    // Transform every cell's value with `f`, producing a new Grid<U> of the same dimensions
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        U: Clone + PartialEq + Eq + Hash,
+        F: Fn(&Cell<T>) -> U,
+    {
+        let data = self
+            .data
+            .iter()
+            .map(|cell| Cell {
+                y: cell.y,
+                x: cell.x,
+                value: f(cell),
+            })
+            .collect();
+        Grid {
+            width: self.width,
+            height: self.height,
+            data,
+        }
     }
 
     // Given a starting coordinate, peek left and right by a given offset returning existing cells
@@ -143,4 +276,282 @@ where
         results.extend(self.peek_diagonal(y, x, offset));
         results
     }
+
+    // Which of a cell's neighbors `evolve` hands to the rule closure
+    fn neighbors(&self, y: usize, x: usize, neighborhood: Neighborhood) -> Vec<Cell<T>> {
+        match neighborhood {
+            Neighborhood::Linear => self.peek_linear(y, x, 1),
+            Neighborhood::Diagonal => self.peek_diagonal(y, x, 1),
+            Neighborhood::All => self.peek_all(y, x, 1),
+        }
+    }
+
+    // Build a new Grid<T> by applying `rule` to every cell and its `neighborhood`. The whole
+    // grid is double-buffered, so `rule` always sees the previous generation rather than a mix
+    // of old and already-updated cells. Cells on the border simply get fewer neighbors in the
+    // slice (peek_* already drops out-of-bounds coordinates), so `rule` must treat missing
+    // neighbors as absent rather than assuming a fixed neighbor count.
+    pub fn evolve<F>(&self, neighborhood: Neighborhood, rule: F) -> Grid<T>
+    where
+        F: Fn(&Cell<T>, &[Cell<T>]) -> T,
+    {
+        let data = self
+            .data
+            .iter()
+            .map(|cell| {
+                let neighbors = self.neighbors(cell.y, cell.x, neighborhood);
+                Cell {
+                    y: cell.y,
+                    x: cell.x,
+                    value: rule(cell, &neighbors),
+                }
+            })
+            .collect();
+        Grid {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    // Apply `evolve` `n` times in a row, returning the final generation
+    pub fn evolve_n<F>(&self, n: usize, neighborhood: Neighborhood, rule: F) -> Grid<T>
+    where
+        F: Fn(&Cell<T>, &[Cell<T>]) -> T,
+    {
+        let mut current = Grid {
+            width: self.width,
+            height: self.height,
+            data: self.data.clone(),
+        };
+        for _ in 0..n {
+            current = current.evolve(neighborhood, &rule);
+        }
+        current
+    }
+
+    // Return maximal horizontal runs of cells satisfying `pred`, row-aware so a run never spans
+    // a line break (e.g. extracting multi-digit numbers from a Grid<char>)
+    pub fn runs_by_row<F>(&self, pred: F) -> Vec<Vec<Cell<T>>>
+    where
+        F: Fn(&Cell<T>) -> bool,
+    {
+        let mut runs = Vec::new();
+        for row in self.rows() {
+            let mut current = Vec::new();
+            for cell in row {
+                if pred(cell) {
+                    current.push(cell.clone());
+                } else if !current.is_empty() {
+                    runs.push(std::mem::take(&mut current));
+                }
+            }
+            if !current.is_empty() {
+                runs.push(current);
+            }
+        }
+        runs
+    }
+
+    // Flood-fill the grid into connected components of cells satisfying `pred`, using
+    // `connectivity` to decide which neighbors join a component
+    pub fn connected_components<F>(
+        &self,
+        pred: F,
+        connectivity: Connectivity,
+    ) -> Vec<Vec<Cell<T>>>
+    where
+        F: Fn(&Cell<T>) -> bool,
+    {
+        let mut visited: HashSet<(usize, usize)> = HashSet::new();
+        let mut components = Vec::new();
+
+        for cell in &self.data {
+            if visited.contains(&(cell.y, cell.x)) || !pred(cell) {
+                continue;
+            }
+            let mut component = Vec::new();
+            let mut queue = VecDeque::new();
+            visited.insert((cell.y, cell.x));
+            queue.push_back(cell.clone());
+            while let Some(current) = queue.pop_front() {
+                let neighbors = match connectivity {
+                    Connectivity::Orthogonal => self.peek_linear(current.y, current.x, 1),
+                    Connectivity::All => self.peek_all(current.y, current.x, 1),
+                };
+                for neighbor in neighbors {
+                    let key = (neighbor.y, neighbor.x);
+                    if !visited.contains(&key) && pred(&neighbor) {
+                        visited.insert(key);
+                        queue.push_back(neighbor);
+                    }
+                }
+                component.push(current);
+            }
+            components.push(component);
+        }
+        components
+    }
+}
+
+// Selects which of Grid's peek_* methods (at offset 1) `evolve` uses to gather a cell's
+// neighbors for its rule closure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    Linear,
+    Diagonal,
+    All,
+}
+
+// Selects 4-connectivity (orthogonal neighbors) or 8-connectivity (orthogonal + diagonal
+// neighbors) for Grid::connected_components
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Orthogonal,
+    All,
+}
+
+impl Grid<char> {
+    // Parse text into a Grid<char>, one cell per character. Every character is a valid
+    // Grid<char> value, but the input can still be a ragged grid, so this panics on that (use
+    // `from_string_with` directly for a non-panicking ragged-row check).
+    pub fn from_chars(text: &str) -> Self {
+        Grid::from_string_with(text, |_y, _x, c| Some(c)).unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl Grid<u32> {
+    // Parse text into a Grid<u32>, treating each character as a digit in the given radix
+    // (e.g. 16 for hex grids, 2 for binary grids) instead of assuming base-10.
+    pub fn from_digits(text: &str, radix: u32) -> Result<Self, GridParseError> {
+        Grid::from_string_with(text, |_y, _x, c| c.to_digit(radix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_string_with_rejects_an_invalid_char() {
+        let err = Grid::from_string_with("12\n4x\n", |_y, _x, c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::InvalidChar {
+                y: 1,
+                x: 1,
+                c: 'x'
+            }
+        );
+    }
+
+    #[test]
+    fn from_digits_parses_a_hex_grid() {
+        let grid = Grid::from_digits("1a\nff\n", 16).unwrap();
+        assert_eq!(grid.get(0, 1).unwrap().value, 10);
+        assert_eq!(grid.get(1, 0).unwrap().value, 15);
+    }
+
+    #[test]
+    fn evolve_gives_border_cells_fewer_linear_neighbors() {
+        let grid = Grid::from_digits("000\n000\n000\n", 10).unwrap();
+        let neighbor_counts = grid.evolve(Neighborhood::Linear, |_cell, neighbors| {
+            neighbors.len() as u32
+        });
+        assert_eq!(neighbor_counts.get(0, 0).unwrap().value, 2); // corner
+        assert_eq!(neighbor_counts.get(0, 1).unwrap().value, 3); // edge
+        assert_eq!(neighbor_counts.get(1, 1).unwrap().value, 4); // center
+    }
+
+    #[test]
+    fn evolve_n_applies_the_rule_n_times() {
+        let grid = Grid::from_digits("0\n", 10).unwrap();
+        let incremented = grid.evolve_n(3, Neighborhood::All, |cell, _neighbors| cell.value + 1);
+        assert_eq!(incremented.get(0, 0).unwrap().value, 3);
+    }
+
+    #[test]
+    fn runs_by_row_does_not_merge_runs_across_a_row_boundary() {
+        let grid = Grid::from_chars("12\n34\n");
+        let runs = grid.runs_by_row(|cell| cell.value.is_ascii_digit());
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].len(), 2);
+        assert_eq!(runs[1].len(), 2);
+    }
+
+    #[test]
+    fn runs_by_row_splits_on_non_matching_cells_within_a_row() {
+        let grid = Grid::from_chars("1a2b\n");
+        let runs = grid.runs_by_row(|cell| cell.value.is_ascii_digit());
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|run| run.len() == 1));
+    }
+
+    #[test]
+    fn connected_components_respects_connectivity() {
+        let grid = Grid::from_chars("aa.\n.a.\n..a\n");
+
+        let orthogonal = grid.connected_components(|cell| cell.value == 'a', Connectivity::Orthogonal);
+        assert_eq!(orthogonal.len(), 2);
+        let mut sizes: Vec<usize> = orthogonal.iter().map(|c| c.len()).collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 3]);
+
+        let all = grid.connected_components(|cell| cell.value == 'a', Connectivity::All);
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].len(), 4);
+    }
+
+    #[test]
+    fn from_rows_trims_only_leading_and_trailing_blank_lines() {
+        let grid = Grid::from_chars("\nab\ncd\n\n");
+        assert_eq!(grid.dimensions(), (2, 2));
+        assert_eq!(grid.get(0, 0).unwrap().value, 'a');
+        assert_eq!(grid.get(1, 1).unwrap().value, 'd');
+    }
+
+    #[test]
+    fn from_rows_rejects_an_interior_blank_line_as_ragged() {
+        let err = Grid::from_string_with("abc\n\ndef\n", |_y, _x, c| Some(c)).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::RaggedRow {
+                y: 1,
+                expected_width: 3,
+                actual_width: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn from_string_with_rejects_ragged_rows_instead_of_panicking_on_get() {
+        // flat y * width + x addressing can't represent rows of different lengths, so a ragged
+        // grid like this is rejected at parse time rather than leaving get()/get_mut() to index
+        // past the end of the backing buffer
+        let err = Grid::from_string_with("abcde\nxyz\n", |_y, _x, c| Some(c)).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::RaggedRow {
+                y: 1,
+                expected_width: 5,
+                actual_width: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn get_mut_set_and_map_mutate_and_transform_in_place() {
+        let mut grid = Grid::from_chars("ab\ncd\n");
+        grid.get_mut(0, 0).unwrap().value = 'z';
+        assert_eq!(grid.get(0, 0).unwrap().value, 'z');
+
+        grid.set(1, 1, 'Z');
+        assert_eq!(grid.get(1, 1).unwrap().value, 'Z');
+        // out-of-bounds set is a no-op, not a panic
+        grid.set(5, 5, 'Z');
+
+        let upper = grid.map(|cell| cell.value.to_ascii_uppercase());
+        assert_eq!(upper.get(0, 1).unwrap().value, 'B');
+        assert_eq!(upper.dimensions(), grid.dimensions());
+    }
 }
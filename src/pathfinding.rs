@@ -0,0 +1,149 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+use crate::{Cell, Grid};
+
+impl<T> Grid<T>
+where
+    T: Clone + PartialEq + Eq + Hash,
+{
+    // Cheapest route from `start` to `goal` via Dijkstra's algorithm, where `cost` gives the
+    // weight of entering a given cell. Returns the total cost and the reconstructed path
+    // (inclusive of `start` and `goal`), or None if `goal` is unreachable.
+    pub fn shortest_path<F>(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        cost: F,
+    ) -> Option<(u64, Vec<Cell<T>>)>
+    where
+        F: Fn(&Cell<T>) -> u64,
+    {
+        self.astar(start, goal, cost, |_| 0)
+    }
+
+    // A* variant of `shortest_path`: `heuristic` must be admissible (e.g. Manhattan distance on
+    // an unweighted grid) so the search can skip exploring unpromising cells on large maps.
+    pub fn astar<F, H>(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        cost: F,
+        heuristic: H,
+    ) -> Option<(u64, Vec<Cell<T>>)>
+    where
+        F: Fn(&Cell<T>) -> u64,
+        H: Fn(&Cell<T>) -> u64,
+    {
+        let (height, width) = self.dimensions();
+        if height == 0 || width == 0 {
+            return None;
+        }
+        let index = |y: usize, x: usize| y * width + x;
+
+        let start_cell = self.get(start.0, start.1)?;
+        self.get(goal.0, goal.1)?;
+
+        let mut dist = vec![u64::MAX; height * width];
+        let mut prev: Vec<Option<(usize, usize)>> = vec![None; height * width];
+        dist[index(start.0, start.1)] = 0;
+
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((heuristic(start_cell), 0u64, start.0, start.1)));
+
+        while let Some(Reverse((_, d, y, x))) = heap.pop() {
+            // stale entry: a shorter path to this cell was already relaxed
+            if d > dist[index(y, x)] {
+                continue;
+            }
+            if (y, x) == goal {
+                let mut path = Vec::new();
+                let mut current = Some((y, x));
+                while let Some((cy, cx)) = current {
+                    path.push(self.get(cy, cx)?.clone());
+                    current = prev[index(cy, cx)];
+                }
+                path.reverse();
+                return Some((d, path));
+            }
+            for neighbor in self.peek_linear(y, x, 1) {
+                let next_index = index(neighbor.y, neighbor.x);
+                let next_dist = d.saturating_add(cost(&neighbor));
+                if next_dist < dist[next_index] {
+                    dist[next_index] = next_dist;
+                    prev[next_index] = Some((y, x));
+                    let priority = next_dist + heuristic(&neighbor);
+                    heap.push(Reverse((priority, next_dist, neighbor.y, neighbor.x)));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    // 1 9 1
+    // 1 9 1
+    // 1 1 1
+    // cheapest route from (0,0) to (2,2) hugs the left column then the bottom row, avoiding
+    // both 9s: (0,0) -> (1,0) -> (2,0) -> (2,1) -> (2,2), costing 1+1+1+1 = 4
+    fn weighted_grid() -> Grid<u32> {
+        Grid::from_digits("191\n191\n111\n", 10).unwrap()
+    }
+
+    #[test]
+    fn shortest_path_finds_cheapest_route_around_high_cost_cells() {
+        let grid = weighted_grid();
+        let (cost, path) = grid
+            .shortest_path((0, 0), (2, 2), |cell| cell.value as u64)
+            .expect("goal is reachable");
+
+        assert_eq!(cost, 4);
+        let coords: Vec<(usize, usize)> = path.iter().map(|cell| (cell.y, cell.x)).collect();
+        assert_eq!(coords, vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra_with_manhattan_heuristic() {
+        let grid = weighted_grid();
+        let manhattan = |goal: (usize, usize)| {
+            move |cell: &crate::Cell<u32>| {
+                (cell.y.abs_diff(goal.0) + cell.x.abs_diff(goal.1)) as u64
+            }
+        };
+
+        let (cost, path) = grid
+            .astar((0, 0), (2, 2), |cell| cell.value as u64, manhattan((2, 2)))
+            .expect("goal is reachable");
+
+        assert_eq!(cost, 4);
+        let coords: Vec<(usize, usize)> = path.iter().map(|cell| (cell.y, cell.x)).collect();
+        assert_eq!(coords, vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn shortest_path_returns_none_for_unreachable_goal() {
+        let grid = weighted_grid();
+        // goal sits outside the grid entirely, so it can never be relaxed
+        assert_eq!(grid.shortest_path((0, 0), (5, 5), |cell| cell.value as u64), None);
+    }
+
+    #[test]
+    fn shortest_path_returns_none_when_goal_is_walled_off() {
+        // row 1 is an impassable wall spanning the full width, cutting the grid in half, so the
+        // in-bounds goal at (2, 2) can never be relaxed once the search exhausts the top half
+        let grid = Grid::from_string_with("111\n999\n111\n", |_y, _x, c| c.to_digit(10)).unwrap();
+        let cost = |cell: &crate::Cell<u32>| {
+            if cell.value == 9 {
+                u64::MAX
+            } else {
+                cell.value as u64
+            }
+        };
+        assert_eq!(grid.shortest_path((0, 0), (2, 2), cost), None);
+    }
+}
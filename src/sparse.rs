@@ -0,0 +1,213 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+const ORTHOGONAL_OFFSETS: [(i64, i64); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+const ALL_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+// A Grid<T> analogue for simulations that grow outward from a seed with no fixed bounds.
+// Backed by a sparse map keyed on signed coordinates so growth in any direction is free, rather
+// than Grid's flat buffer sized to a fixed width/height.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T>
+where
+    T: Clone + PartialEq + Eq + Hash,
+{
+    cells: HashMap<(i64, i64), T>,
+}
+
+impl<T> SparseGrid<T>
+where
+    T: Clone + PartialEq + Eq + Hash,
+{
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, y: i64, x: i64) -> Option<&T> {
+        self.cells.get(&(y, x))
+    }
+
+    pub fn insert(&mut self, y: i64, x: i64, value: T) -> Option<T> {
+        self.cells.insert((y, x), value)
+    }
+
+    pub fn remove(&mut self, y: i64, x: i64) -> Option<T> {
+        self.cells.remove(&(y, x))
+    }
+
+    // Mirrors Grid::peek_linear but over signed coordinates: existing orthogonal neighbors as
+    // (y, x, value) triples
+    pub fn peek_linear(&self, y: i64, x: i64) -> Vec<(i64, i64, T)> {
+        self.peek_offsets(y, x, &ORTHOGONAL_OFFSETS)
+    }
+
+    // Mirrors Grid::peek_all but over signed coordinates: existing orthogonal + diagonal
+    // neighbors as (y, x, value) triples
+    pub fn peek_all(&self, y: i64, x: i64) -> Vec<(i64, i64, T)> {
+        self.peek_offsets(y, x, &ALL_OFFSETS)
+    }
+
+    fn peek_offsets(&self, y: i64, x: i64, offsets: &[(i64, i64)]) -> Vec<(i64, i64, T)> {
+        offsets
+            .iter()
+            .filter_map(|(dy, dx)| {
+                let (ny, nx) = (y + dy, x + dx);
+                self.get(ny, nx).map(|value| (ny, nx, value.clone()))
+            })
+            .collect()
+    }
+
+    // Bounding box of populated coordinates as (min_y, max_y, min_x, max_x), or None when empty
+    pub fn bounds(&self) -> Option<(i64, i64, i64, i64)> {
+        let mut keys = self.cells.keys();
+        let &(first_y, first_x) = keys.next()?;
+        let (mut min_y, mut max_y, mut min_x, mut max_x) = (first_y, first_y, first_x, first_x);
+        for &(y, x) in keys {
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+        }
+        Some((min_y, max_y, min_x, max_x))
+    }
+
+    // Step the simulation forward one generation. Candidates are every populated cell plus its
+    // 8 neighbors; `rule` receives the candidate's current value (if any) and its populated
+    // neighbor values, and returns the next value or None to leave the cell empty. Because
+    // candidates include each populated cell's neighbors, the active region can grow by one
+    // ring per generation without preallocating a bounding box.
+    pub fn evolve<F>(&self, rule: F) -> SparseGrid<T>
+    where
+        F: Fn(Option<&T>, &[T]) -> Option<T>,
+    {
+        let mut candidates: HashSet<(i64, i64)> = HashSet::new();
+        for &(y, x) in self.cells.keys() {
+            candidates.insert((y, x));
+            for (dy, dx) in ALL_OFFSETS {
+                candidates.insert((y + dy, x + dx));
+            }
+        }
+
+        let mut cells = HashMap::new();
+        for (y, x) in candidates {
+            let current = self.get(y, x);
+            let neighbors: Vec<T> = ALL_OFFSETS
+                .iter()
+                .filter_map(|(dy, dx)| self.get(y + dy, x + dx).cloned())
+                .collect();
+            if let Some(value) = rule(current, &neighbors) {
+                cells.insert((y, x), value);
+            }
+        }
+        SparseGrid { cells }
+    }
+
+    // Apply `evolve` `n` times in a row, returning the final generation
+    pub fn evolve_n<F>(&self, n: usize, rule: F) -> SparseGrid<T>
+    where
+        F: Fn(Option<&T>, &[T]) -> Option<T>,
+    {
+        let mut current = self.clone();
+        for _ in 0..n {
+            current = current.evolve(&rule);
+        }
+        current
+    }
+}
+
+impl<T> Default for SparseGrid<T>
+where
+    T: Clone + PartialEq + Eq + Hash,
+{
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_get_and_remove_round_trip_a_value() {
+        let mut grid: SparseGrid<char> = SparseGrid::new();
+        assert_eq!(grid.insert(0, 0, 'a'), None);
+        assert_eq!(grid.get(0, 0), Some(&'a'));
+        assert_eq!(grid.insert(0, 0, 'b'), Some('a'));
+        assert_eq!(grid.remove(0, 0), Some('b'));
+        assert_eq!(grid.get(0, 0), None);
+    }
+
+    #[test]
+    fn peek_linear_and_peek_all_only_return_populated_neighbors() {
+        let mut grid: SparseGrid<char> = SparseGrid::new();
+        grid.insert(0, 0, 'n');
+        grid.insert(0, 1, 's'); // orthogonal neighbor of (0,0)
+        grid.insert(1, 1, 'd'); // diagonal-only neighbor of (0,0)
+
+        let linear = grid.peek_linear(0, 0);
+        assert_eq!(linear.len(), 1);
+        assert!(linear.contains(&(0, 1, 's')));
+
+        let all = grid.peek_all(0, 0);
+        assert_eq!(all.len(), 2);
+        assert!(all.contains(&(1, 1, 'd')));
+    }
+
+    #[test]
+    fn bounds_is_none_when_empty_and_the_bounding_box_otherwise() {
+        let mut grid: SparseGrid<char> = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(-2, 3, 'a');
+        grid.insert(5, -1, 'b');
+        assert_eq!(grid.bounds(), Some((-2, 5, -1, 3)));
+    }
+
+    #[test]
+    fn evolve_grows_into_unpopulated_neighbors() {
+        let mut grid: SparseGrid<u32> = SparseGrid::new();
+        grid.insert(0, 0, 1);
+
+        // a cell becomes populated with the count of its populated neighbors, so the single
+        // seed cell spawns 8 neighbors each reporting exactly 1 populated neighbor
+        let next = grid.evolve(|_current, neighbors| {
+            if neighbors.is_empty() {
+                None
+            } else {
+                Some(neighbors.len() as u32)
+            }
+        });
+        assert_eq!(next.len(), 8);
+        assert_eq!(next.get(1, 1), Some(&1));
+    }
+
+    #[test]
+    fn evolve_n_applies_the_rule_n_times() {
+        let mut grid: SparseGrid<u32> = SparseGrid::new();
+        grid.insert(0, 0, 1);
+
+        let grown = grid.evolve_n(2, |current, _neighbors| Some(current.copied().unwrap_or(0) + 1));
+        assert_eq!(grown.get(0, 0), Some(&3));
+    }
+}